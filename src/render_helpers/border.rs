@@ -15,26 +15,147 @@ use crate::backend::tty::{TtyFrame, TtyRenderer, TtyRendererError};
 /// Renders a wide variety of borders and border parts.
 ///
 /// This includes:
-/// * sub- or super-rect of an angled linear gradient like CSS linear-gradient(angle, a, b).
+/// * sub- or super-rect of a linear or radial, multi-stop gradient like CSS
+///   linear-gradient(angle, a, b, c) or radial-gradient().
 /// * corner rounding.
-/// * as a background rectangle and as parts of a border line.
+/// * as a background rectangle and as parts of a solid, dashed or dotted border line.
 #[derive(Debug, Clone)]
 pub struct BorderRenderElement {
     inner: ShaderRenderElement,
     params: Parameters,
 }
 
+/// Which kind of color gradient a [`BorderRenderElement`] projects onto its area.
+///
+/// This mirrors the two CSS gradient shapes we care about: an angled linear ramp, and a
+/// radial ramp expanding between two circles (the general two-circle form used for e.g.
+/// `radial-gradient(circle at ...)` with differing start/end centers and radii).
 #[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GradientKind {
+    Linear {
+        angle: f32,
+    },
+    Radial {
+        start_center: Vec2,
+        start_radius: f32,
+        end_center: Vec2,
+        end_radius: f32,
+        aspect: f32,
+    },
+}
+
+impl Default for GradientKind {
+    fn default() -> Self {
+        Self::Linear { angle: 0. }
+    }
+}
+
+/// Maximum number of color stops a single gradient can carry.
+///
+/// The shader uploads stops as fixed-size uniform arrays, so this is a hard cap; `new`/
+/// `update` truncate longer stop lists, matching CSS `linear-gradient()`'s own de-facto
+/// engine limits.
+pub const MAX_GRADIENT_STOPS: usize = 8;
+
+/// A single color stop in a multi-stop gradient, analogous to a `<color-stop>` in CSS
+/// `linear-gradient(a, b, c, ...)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GradientStop {
+    pub offset: f32,
+    pub color: [f32; 4],
+}
+
+fn truncate_stops(stops: &[GradientStop]) -> Vec<GradientStop> {
+    if stops.len() > MAX_GRADIENT_STOPS {
+        stops[..MAX_GRADIENT_STOPS].to_vec()
+    } else {
+        stops.to_vec()
+    }
+}
+
+/// Converts a radial gradient circle center, normalized within `gradient_area`, into the
+/// area-local physical frame the fragment position lives in.
+fn radial_gradient_center(normalized: Vec2, grad_area_size: Vec2, grad_offset: Vec2) -> Vec2 {
+    normalized * grad_area_size - grad_offset
+}
+
+/// Pads a (possibly empty or oversized) stop list out to fixed-capacity uniform arrays,
+/// repeating the last stop into the unused tail slots so the shader's clamp-to-edge
+/// behavior is correct without needing to special-case `stop_count` there.
+fn pad_stops(
+    stops: &[GradientStop],
+) -> (
+    [f32; MAX_GRADIENT_STOPS],
+    [[f32; 4]; MAX_GRADIENT_STOPS],
+    i32,
+) {
+    let stops = &stops[..stops.len().min(MAX_GRADIENT_STOPS)];
+    let mut stop_offsets = [1f32; MAX_GRADIENT_STOPS];
+    let mut stop_colors = [[0f32; 4]; MAX_GRADIENT_STOPS];
+    for (i, stop) in stops.iter().enumerate() {
+        stop_offsets[i] = stop.offset;
+        stop_colors[i] = stop.color;
+    }
+    let last = stops.last().copied().unwrap_or(GradientStop {
+        offset: 0.,
+        color: [0.; 4],
+    });
+    for i in stops.len()..MAX_GRADIENT_STOPS {
+        stop_offsets[i] = last.offset;
+        stop_colors[i] = last.color;
+    }
+    (stop_offsets, stop_colors, stops.len() as i32)
+}
+
+/// Line style to draw the border in, analogous to the CSS `border-style` keywords we
+/// support.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BorderStyle {
+    Solid,
+    /// Alternating `dash`-long filled and `gap`-long empty segments, in logical pixels.
+    Dashed {
+        dash: f32,
+        gap: f32,
+    },
+    Dotted,
+}
+
+impl Default for BorderStyle {
+    fn default() -> Self {
+        Self::Solid
+    }
+}
+
+/// Resolves a [`BorderStyle`] to the `(kind, dash_length, gap_length)` uniforms, scaling the
+/// dash/gap lengths to physical pixels. A degenerate `Dashed { dash: 0., gap: 0. }` (which
+/// would make the shader's `arclen / (dash + gap)` divide by zero) falls back to solid.
+fn border_style_uniforms(style: BorderStyle, scale_x: f32) -> (i32, f32, f32) {
+    match style {
+        BorderStyle::Solid => (0, 0., 0.),
+        BorderStyle::Dashed { dash, gap } => {
+            let dash = dash.max(0.);
+            let gap = gap.max(0.);
+            if dash + gap > 0. {
+                (1, dash * scale_x, gap * scale_x)
+            } else {
+                (0, 0., 0.)
+            }
+        }
+        BorderStyle::Dotted => (2, 0., 0.),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 struct Parameters {
     scale: Scale<f64>,
     area: Rectangle<i32, Logical>,
     gradient_area: Rectangle<i32, Logical>,
-    color_from: [f32; 4],
-    color_to: [f32; 4],
-    angle: f32,
+    stops: Vec<GradientStop>,
+    gradient: GradientKind,
     geometry: Rectangle<i32, Logical>,
     border_width: f32,
     corner_radius: CornerRadius,
+    border_style: BorderStyle,
 }
 
 impl BorderRenderElement {
@@ -44,12 +165,12 @@ impl BorderRenderElement {
         scale: Scale<f64>,
         area: Rectangle<i32, Logical>,
         gradient_area: Rectangle<i32, Logical>,
-        color_from: [f32; 4],
-        color_to: [f32; 4],
-        angle: f32,
+        stops: &[GradientStop],
+        gradient: GradientKind,
         geometry: Rectangle<i32, Logical>,
         border_width: f32,
         corner_radius: CornerRadius,
+        border_style: BorderStyle,
     ) -> Self {
         let mut inner = ShaderRenderElement::empty(Kind::Unspecified);
         inner.update_shader(Some(shader));
@@ -59,12 +180,12 @@ impl BorderRenderElement {
                 scale,
                 area,
                 gradient_area,
-                color_from,
-                color_to,
-                angle,
+                stops: truncate_stops(stops),
+                gradient,
                 geometry,
                 border_width,
                 corner_radius,
+                border_style,
             },
         };
         rv.update_inner();
@@ -79,12 +200,12 @@ impl BorderRenderElement {
                 scale: Scale::from(1.),
                 area: Default::default(),
                 gradient_area: Default::default(),
-                color_from: Default::default(),
-                color_to: Default::default(),
-                angle: 0.,
+                stops: Vec::new(),
+                gradient: Default::default(),
                 geometry: Default::default(),
                 border_width: 0.,
                 corner_radius: Default::default(),
+                border_style: Default::default(),
             },
         }
     }
@@ -99,23 +220,23 @@ impl BorderRenderElement {
         scale: Scale<f64>,
         area: Rectangle<i32, Logical>,
         gradient_area: Rectangle<i32, Logical>,
-        color_from: [f32; 4],
-        color_to: [f32; 4],
-        angle: f32,
+        stops: &[GradientStop],
+        gradient: GradientKind,
         geometry: Rectangle<i32, Logical>,
         border_width: f32,
         corner_radius: CornerRadius,
+        border_style: BorderStyle,
     ) {
         let params = Parameters {
             scale,
             area,
             gradient_area,
-            color_from,
-            color_to,
-            angle,
+            stops: truncate_stops(stops),
+            gradient,
             geometry,
             border_width,
             corner_radius,
+            border_style,
         };
         if self.params == params {
             return;
@@ -130,29 +251,63 @@ impl BorderRenderElement {
             scale,
             area,
             gradient_area,
-            color_from,
-            color_to,
-            angle,
+            ref stops,
+            gradient,
             geometry,
             border_width,
             corner_radius,
+            border_style,
         } = self.params;
 
-        let grad_offset = (area.loc - gradient_area.loc).to_f64().to_physical(scale);
-
-        let grad_dir = Vec2::from_angle(angle);
-
         let grad_area_size = gradient_area.size.to_f64().to_physical(scale);
         let (w, h) = (grad_area_size.w as f32, grad_area_size.h as f32);
 
-        let mut grad_area_diag = Vec2::new(w, h);
-        if (grad_dir.x < 0. && 0. <= grad_dir.y) || (0. <= grad_dir.x && grad_dir.y < 0.) {
-            grad_area_diag.x = -w;
-        }
-
-        let mut grad_vec = grad_area_diag.project_onto(grad_dir);
-        if grad_dir.y <= 0. {
-            grad_vec = -grad_vec;
+        // Linear-only uniforms; left at zero for the radial case, where the shader ignores
+        // them in favor of `grad_is_radial` and the two-circle uniforms below.
+        let mut grad_offset = (area.loc - gradient_area.loc).to_f64().to_physical(scale);
+        let mut grad_vec = Vec2::ZERO;
+        let mut grad_is_radial = 0f32;
+        let mut grad_start_center = Vec2::ZERO;
+        let mut grad_start_radius = 0f32;
+        let mut grad_end_center = Vec2::ZERO;
+        let mut grad_end_radius = 0f32;
+        let mut grad_aspect = 1f32;
+
+        match gradient {
+            GradientKind::Linear { angle } => {
+                let grad_dir = Vec2::from_angle(angle);
+
+                let mut grad_area_diag = Vec2::new(w, h);
+                if (grad_dir.x < 0. && 0. <= grad_dir.y) || (0. <= grad_dir.x && grad_dir.y < 0.) {
+                    grad_area_diag.x = -w;
+                }
+
+                grad_vec = grad_area_diag.project_onto(grad_dir);
+                if grad_dir.y <= 0. {
+                    grad_vec = -grad_vec;
+                }
+            }
+            GradientKind::Radial {
+                start_center,
+                start_radius,
+                end_center,
+                end_radius,
+                aspect,
+            } => {
+                grad_is_radial = 1.;
+                grad_aspect = aspect;
+
+                let offset = Vec2::new(grad_offset.x as f32, grad_offset.y as f32);
+                let size = Vec2::new(w, h);
+                grad_start_center = radial_gradient_center(start_center, size, offset);
+                grad_end_center = radial_gradient_center(end_center, size, offset);
+                grad_start_radius = start_radius * scale.x as f32;
+                grad_end_radius = end_radius * scale.x as f32;
+
+                // The circle centers already carry the area offset; the shader doesn't
+                // need the separate `grad_offset` uniform in the radial path.
+                grad_offset = Default::default();
+            }
         }
 
         let area_physical = area.to_physical_precise_round(scale);
@@ -165,23 +320,40 @@ impl BorderRenderElement {
 
         let input_to_geo =
             Mat3::from_scale(area_size) * Mat3::from_translation((area_loc - geo_loc) / area_size);
+        // TODO: scale x/y radii independently once `niri_config::CornerRadius` grows a
+        // second axis; for now it only carries one radius per corner.
         let corner_radius = corner_radius.scaled_by(scale.x as f32);
         let border_width = border_width * scale.x as f32;
 
+        let (border_style_kind, dash_length, gap_length) =
+            border_style_uniforms(border_style, scale.x as f32);
+
+        let (stop_offsets, stop_colors, stop_count) = pad_stops(stops);
+
         self.inner.update(
             area,
             area.size.to_f64().to_buffer(scale, Transform::Normal),
             None,
             vec![
-                Uniform::new("color_from", color_from),
-                Uniform::new("color_to", color_to),
+                Uniform::new("stop_offsets", stop_offsets),
+                Uniform::new("stop_colors", stop_colors.concat()),
+                Uniform::new("stop_count", stop_count),
                 Uniform::new("grad_offset", (grad_offset.x as f32, grad_offset.y as f32)),
                 Uniform::new("grad_width", w),
                 Uniform::new("grad_vec", grad_vec.to_array()),
+                Uniform::new("grad_is_radial", grad_is_radial),
+                Uniform::new("grad_start_center", grad_start_center.to_array()),
+                Uniform::new("grad_start_radius", grad_start_radius),
+                Uniform::new("grad_end_center", grad_end_center.to_array()),
+                Uniform::new("grad_end_radius", grad_end_radius),
+                Uniform::new("grad_aspect", grad_aspect),
                 mat3_uniform("input_to_geo", input_to_geo),
                 Uniform::new("geo_size", geo_size.to_array()),
                 Uniform::new("outer_radius", <[f32; 4]>::from(corner_radius)),
                 Uniform::new("border_width", border_width),
+                Uniform::new("border_style", border_style_kind),
+                Uniform::new("dash_length", dash_length),
+                Uniform::new("gap_length", gap_length),
             ],
             HashMap::new(),
         );
@@ -274,4 +446,87 @@ impl<'render> RenderElement<TtyRenderer<'render>> for BorderRenderElement {
     fn underlying_storage(&self, renderer: &mut TtyRenderer<'render>) -> Option<UnderlyingStorage> {
         self.inner.underlying_storage(renderer)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn radial_gradient_center_matches_area_when_offset_is_zero() {
+        let center = radial_gradient_center(Vec2::new(0.5, 0.5), Vec2::new(100., 50.), Vec2::ZERO);
+        assert_eq!(center, Vec2::new(50., 25.));
+    }
+
+    #[test]
+    fn radial_gradient_center_subtracts_area_gradient_area_offset() {
+        // `area` sits 10px right of and 4px below `gradient_area`'s origin, so a circle
+        // normalized at `gradient_area`'s center must land 10px left of / 4px above the
+        // naive `area`-relative center.
+        let offset = Vec2::new(10., 4.);
+        let center = radial_gradient_center(Vec2::new(0.5, 0.5), Vec2::new(100., 50.), offset);
+        assert_eq!(center, Vec2::new(40., 21.));
+    }
+
+    #[test]
+    fn pad_stops_repeats_last_stop_into_unused_slots() {
+        let stops = [
+            GradientStop {
+                offset: 0.,
+                color: [1., 0., 0., 1.],
+            },
+            GradientStop {
+                offset: 1.,
+                color: [0., 0., 1., 1.],
+            },
+        ];
+        let (offsets, colors, count) = pad_stops(&stops);
+        assert_eq!(count, 2);
+        assert_eq!(&offsets[..2], &[0., 1.]);
+        assert_eq!(&colors[..2], &[[1., 0., 0., 1.], [0., 0., 1., 1.]]);
+        for i in 2..MAX_GRADIENT_STOPS {
+            assert_eq!(offsets[i], 1.);
+            assert_eq!(colors[i], [0., 0., 1., 1.]);
+        }
+    }
+
+    #[test]
+    fn pad_stops_handles_no_stops() {
+        let (offsets, colors, count) = pad_stops(&[]);
+        assert_eq!(count, 0);
+        assert_eq!(offsets, [0.; MAX_GRADIENT_STOPS]);
+        assert_eq!(colors, [[0.; 4]; MAX_GRADIENT_STOPS]);
+    }
+
+    #[test]
+    fn border_style_uniforms_solid_and_dotted() {
+        assert_eq!(border_style_uniforms(BorderStyle::Solid, 2.), (0, 0., 0.));
+        assert_eq!(border_style_uniforms(BorderStyle::Dotted, 2.), (2, 0., 0.));
+    }
+
+    #[test]
+    fn border_style_uniforms_scales_dash_and_gap() {
+        let style = BorderStyle::Dashed { dash: 4., gap: 2. };
+        assert_eq!(border_style_uniforms(style, 2.), (1, 8., 4.));
+    }
+
+    #[test]
+    fn border_style_uniforms_degenerate_dash_falls_back_to_solid() {
+        let style = BorderStyle::Dashed { dash: 0., gap: 0. };
+        assert_eq!(border_style_uniforms(style, 2.), (0, 0., 0.));
+    }
+
+    #[test]
+    fn border_style_uniforms_clamps_negative_dash_or_gap() {
+        // A negative `dash` or `gap` is clamped to zero before the `dash + gap > 0.` guard,
+        // so only a pattern with no positive length left falls back to solid.
+        let style = BorderStyle::Dashed { dash: -1., gap: 5. };
+        assert_eq!(border_style_uniforms(style, 2.), (1, 0., 10.));
+
+        let style = BorderStyle::Dashed {
+            dash: -1.,
+            gap: -5.,
+        };
+        assert_eq!(border_style_uniforms(style, 2.), (0, 0., 0.));
+    }
+}
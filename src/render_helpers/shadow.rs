@@ -0,0 +1,274 @@
+use std::collections::HashMap;
+
+use glam::Vec2;
+use niri_config::CornerRadius;
+use smithay::backend::renderer::element::{Element, Id, Kind, RenderElement, UnderlyingStorage};
+use smithay::backend::renderer::gles::{GlesError, GlesFrame, GlesRenderer, Uniform};
+use smithay::backend::renderer::utils::{CommitCounter, DamageSet};
+use smithay::utils::{Buffer, Logical, Physical, Rectangle, Scale, Transform};
+
+use super::renderer::NiriRenderer;
+use super::shader_element::{ShaderProgram, ShaderRenderElement};
+use super::shaders::Shaders;
+use crate::backend::tty::{TtyFrame, TtyRenderer, TtyRendererError};
+
+/// Renders a soft drop shadow behind a rounded rectangle, analogous to a CSS `box-shadow`.
+///
+/// The shadow is computed analytically in the fragment shader (a closed-form Gaussian blur
+/// of a rounded rect), so it needs no separate blur pass or intermediate framebuffer.
+#[derive(Debug, Clone)]
+pub struct ShadowRenderElement {
+    inner: ShaderRenderElement,
+    params: Parameters,
+}
+
+/// Grows `geo_loc`/`geo_size` (the window geometry, in physical pixels relative to the area
+/// origin) outward by `spread` on every side.
+fn grow_by_spread(geo_loc: Vec2, geo_size: Vec2, spread: f32) -> (Vec2, Vec2) {
+    (
+        geo_loc - Vec2::splat(spread),
+        geo_size + Vec2::splat(2. * spread),
+    )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Parameters {
+    scale: Scale<f64>,
+    area: Rectangle<i32, Logical>,
+    geometry: Rectangle<i32, Logical>,
+    corner_radius: CornerRadius,
+    blur_sigma: f32,
+    spread: f32,
+    color: [f32; 4],
+}
+
+impl ShadowRenderElement {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        shader: &ShaderProgram,
+        scale: Scale<f64>,
+        area: Rectangle<i32, Logical>,
+        geometry: Rectangle<i32, Logical>,
+        corner_radius: CornerRadius,
+        blur_sigma: f32,
+        spread: f32,
+        color: [f32; 4],
+    ) -> Self {
+        let mut inner = ShaderRenderElement::empty(Kind::Unspecified);
+        inner.update_shader(Some(shader));
+        let mut rv = Self {
+            inner,
+            params: Parameters {
+                scale,
+                area,
+                geometry,
+                corner_radius,
+                blur_sigma,
+                spread,
+                color,
+            },
+        };
+        rv.update_inner();
+        rv
+    }
+
+    pub fn empty() -> Self {
+        let inner = ShaderRenderElement::empty(Kind::Unspecified);
+        Self {
+            inner,
+            params: Parameters {
+                scale: Scale::from(1.),
+                area: Default::default(),
+                geometry: Default::default(),
+                corner_radius: Default::default(),
+                blur_sigma: 0.,
+                spread: 0.,
+                color: Default::default(),
+            },
+        }
+    }
+
+    pub fn update_shader(&mut self, shader: Option<&ShaderProgram>) {
+        self.inner.update_shader(shader);
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn update(
+        &mut self,
+        scale: Scale<f64>,
+        area: Rectangle<i32, Logical>,
+        geometry: Rectangle<i32, Logical>,
+        corner_radius: CornerRadius,
+        blur_sigma: f32,
+        spread: f32,
+        color: [f32; 4],
+    ) {
+        let params = Parameters {
+            scale,
+            area,
+            geometry,
+            corner_radius,
+            blur_sigma,
+            spread,
+            color,
+        };
+        if self.params == params {
+            return;
+        }
+
+        self.params = params;
+        self.update_inner();
+    }
+
+    fn update_inner(&mut self) {
+        let Parameters {
+            scale,
+            area,
+            geometry,
+            corner_radius,
+            blur_sigma,
+            spread,
+            color,
+        } = self.params;
+
+        let area_physical = area.to_physical_precise_round(scale);
+        let area_loc = Vec2::new(area_physical.loc.x, area_physical.loc.y);
+
+        // The shadow shape is the window geometry grown by `spread` on every side, in
+        // physical pixels relative to the rendered area's own origin.
+        let spread_physical = spread * scale.x as f32;
+        let geo = geometry.to_physical_precise_round(scale);
+        let (geo_loc, geo_size) = grow_by_spread(
+            Vec2::new(geo.loc.x, geo.loc.y) - area_loc,
+            Vec2::new(geo.size.w, geo.size.h),
+            spread_physical,
+        );
+
+        // TODO: match `BorderRenderElement`'s per-axis corner scaling once
+        // `niri_config::CornerRadius` grows a second axis; for now both elements share the
+        // same single-radius-per-corner representation.
+        let corner_radius = corner_radius.scaled_by(scale.x as f32);
+        let blur_sigma = blur_sigma * scale.x as f32;
+
+        self.inner.update(
+            area,
+            area.size.to_f64().to_buffer(scale, Transform::Normal),
+            None,
+            vec![
+                Uniform::new("geo_loc", geo_loc.to_array()),
+                Uniform::new("geo_size", geo_size.to_array()),
+                Uniform::new("corner_radius", <[f32; 4]>::from(corner_radius)),
+                Uniform::new("blur_sigma", blur_sigma),
+                Uniform::new("color", color),
+            ],
+            HashMap::new(),
+        );
+    }
+
+    pub fn has_shader(&self) -> bool {
+        self.inner.has_shader()
+    }
+
+    pub fn shader(renderer: &mut impl NiriRenderer) -> Option<&ShaderProgram> {
+        Shaders::get(renderer).shadow.as_ref()
+    }
+}
+
+impl Default for ShadowRenderElement {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl Element for ShadowRenderElement {
+    fn id(&self) -> &Id {
+        self.inner.id()
+    }
+
+    fn current_commit(&self) -> CommitCounter {
+        self.inner.current_commit()
+    }
+
+    fn geometry(&self, scale: Scale<f64>) -> Rectangle<i32, Physical> {
+        self.inner.geometry(scale)
+    }
+
+    fn transform(&self) -> Transform {
+        self.inner.transform()
+    }
+
+    fn src(&self) -> Rectangle<f64, Buffer> {
+        self.inner.src()
+    }
+
+    fn damage_since(
+        &self,
+        scale: Scale<f64>,
+        commit: Option<CommitCounter>,
+    ) -> DamageSet<i32, Physical> {
+        self.inner.damage_since(scale, commit)
+    }
+
+    fn opaque_regions(&self, scale: Scale<f64>) -> Vec<Rectangle<i32, Physical>> {
+        self.inner.opaque_regions(scale)
+    }
+
+    fn alpha(&self) -> f32 {
+        self.inner.alpha()
+    }
+
+    fn kind(&self) -> Kind {
+        self.inner.kind()
+    }
+}
+
+impl RenderElement<GlesRenderer> for ShadowRenderElement {
+    fn draw(
+        &self,
+        frame: &mut GlesFrame<'_>,
+        src: Rectangle<f64, Buffer>,
+        dst: Rectangle<i32, Physical>,
+        damage: &[Rectangle<i32, Physical>],
+    ) -> Result<(), GlesError> {
+        RenderElement::<GlesRenderer>::draw(&self.inner, frame, src, dst, damage)
+    }
+
+    fn underlying_storage(&self, renderer: &mut GlesRenderer) -> Option<UnderlyingStorage> {
+        self.inner.underlying_storage(renderer)
+    }
+}
+
+impl<'render> RenderElement<TtyRenderer<'render>> for ShadowRenderElement {
+    fn draw(
+        &self,
+        frame: &mut TtyFrame<'_, '_>,
+        src: Rectangle<f64, Buffer>,
+        dst: Rectangle<i32, Physical>,
+        damage: &[Rectangle<i32, Physical>],
+    ) -> Result<(), TtyRendererError<'render>> {
+        RenderElement::<TtyRenderer<'_>>::draw(&self.inner, frame, src, dst, damage)
+    }
+
+    fn underlying_storage(&self, renderer: &mut TtyRenderer<'render>) -> Option<UnderlyingStorage> {
+        self.inner.underlying_storage(renderer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grow_by_spread_expands_on_every_side() {
+        let (loc, size) = grow_by_spread(Vec2::new(10., 20.), Vec2::new(100., 50.), 5.);
+        assert_eq!(loc, Vec2::new(5., 15.));
+        assert_eq!(size, Vec2::new(110., 60.));
+    }
+
+    #[test]
+    fn grow_by_spread_is_a_no_op_for_zero_spread() {
+        let (loc, size) = grow_by_spread(Vec2::new(10., 20.), Vec2::new(100., 50.), 0.);
+        assert_eq!(loc, Vec2::new(10., 20.));
+        assert_eq!(size, Vec2::new(100., 50.));
+    }
+}